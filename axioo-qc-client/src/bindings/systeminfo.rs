@@ -6,7 +6,9 @@ use libloading::{Library, Symbol};
 use once_cell::sync::Lazy;
 use once_cell::sync::OnceCell;
 use std::ffi::{c_char, CStr};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
+use tokio::sync::broadcast;
 
 // Type definition for a callback function that receives system information.
 type SystemInfoCallback = extern "C" fn(*const c_char);
@@ -28,12 +30,20 @@ static LIBRARY: OnceCell<Mutex<Library>> = OnceCell::new();
 static LOG_BUFFER: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
 static CALLBACK_BUFFER: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
+// Notifies subscribers every time the native library pushes a change into
+// CALLBACK_BUFFER, so callers can react to hardware changes instead of polling.
+static CHANGE_NOTIFIER: Lazy<broadcast::Sender<()>> = Lazy::new(|| broadcast::channel(16).0);
+
+// Runtime debug mode flag, seeded from the `DEBUG_MODE` constant at startup but
+// toggleable afterwards (e.g. from the control channel) via `set_debug_mode`.
+static DEBUG_MODE_FLAG: AtomicBool = AtomicBool::new(DEBUG_MODE);
+
 // Callback function to handle system information received from the library.
 extern "C" fn system_info_callback(json_data: *const c_char) {
     unsafe {
         if !json_data.is_null() {
             if let Ok(data) = CStr::from_ptr(json_data).to_str() {
-                if DEBUG_MODE {
+                if is_debug_mode() {
                     println!("System info callback received: {}", data);
                 }
 
@@ -42,6 +52,10 @@ extern "C" fn system_info_callback(json_data: *const c_char) {
                 } else {
                     eprintln!("Failed to lock callback buffer");
                 }
+
+                // Best-effort: no receivers yet (or all dropped) just means nobody's
+                // listening for push updates right now, which is fine.
+                let _ = CHANGE_NOTIFIER.send(());
             }
         }
     }
@@ -49,7 +63,7 @@ extern "C" fn system_info_callback(json_data: *const c_char) {
 
 // Callback function to handle logging messages from the library.
 extern "C" fn log_callback(level: *const c_char, message: *const c_char) {
-    if !DEBUG_MODE {
+    if !is_debug_mode() {
         return;
     }
     unsafe {
@@ -115,9 +129,14 @@ pub fn get_system_info() -> Result<String> {
     }
 }
 
-// Retrieves the last error code from the library.
+// Retrieves the last error code from the library. Returns `-1` if the library
+// hasn't been loaded yet instead of panicking, since this is called from the crash
+// handler, which can run before `init_library()` (e.g. if `init_library()` itself panics).
 pub fn get_last_error() -> i32 {
-    let lib = LIBRARY.get().unwrap().lock().unwrap();
+    let Some(library) = LIBRARY.get() else {
+        return -1;
+    };
+    let lib = library.lock().unwrap();
     unsafe {
         let get_error: Symbol<GetSystemInfoLastErrorT> =
             lib.get(b"GetSystemInfoLastError").unwrap();
@@ -125,9 +144,14 @@ pub fn get_last_error() -> i32 {
     }
 }
 
-// Retrieves the last error message from the library.
+// Retrieves the last error message from the library. Returns a sentinel string if
+// the library hasn't been loaded yet instead of panicking, for the same reason as
+// `get_last_error`.
 pub fn get_error_message() -> String {
-    let lib = LIBRARY.get().unwrap().lock().unwrap();
+    let Some(library) = LIBRARY.get() else {
+        return "<library not loaded>".to_string();
+    };
+    let lib = library.lock().unwrap();
     unsafe {
         let get_message: Symbol<GetSystemInfoErrorMessageT> =
             lib.get(b"GetSystemInfoErrorMessage").unwrap();
@@ -180,6 +204,30 @@ pub fn get_logs() -> Vec<String> {
         .unwrap_or_default()
 }
 
+// Reports whether debug mode is currently on, reflecting the last `set_debug_mode`
+// call (or the `DEBUG_MODE` constant if it's never been changed).
+pub fn is_debug_mode() -> bool {
+    DEBUG_MODE_FLAG.load(Ordering::Relaxed)
+}
+
+// Toggles debug mode at runtime, propagating the change into the native library via
+// `SetDebugMode` so its own logging follows the same setting.
+pub fn set_debug_mode(enabled: bool) -> Result<()> {
+    let lib = LIBRARY.get().unwrap().lock().unwrap();
+    unsafe {
+        let set_debug: Symbol<SetDebugModeT> = lib.get(b"SetDebugMode")?;
+        set_debug(enabled);
+    }
+    DEBUG_MODE_FLAG.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+// Subscribes to hardware change notifications fired by the native library's callback,
+// so callers can refresh and broadcast `get_system_info()` only when something changed.
+pub fn subscribe_changes() -> broadcast::Receiver<()> {
+    CHANGE_NOTIFIER.subscribe()
+}
+
 // Retrieves and clears the callback data.
 pub fn get_callback_data() -> Vec<String> {
     CALLBACK_BUFFER
@@ -0,0 +1,173 @@
+// Opt-in binary frame codec: a length-delimited, optionally zstd-compressed
+// alternative to sending raw JSON text over the WebSocket. Clients request it via
+// the `axioo-binary-v1` WebSocket subprotocol; plain `Message::Text` JSON stays the
+// default so the connection is still readable in a debugger or browser console.
+//
+// Wire format: `[len: u32 BE][header: u8][body: len - 1 bytes]`, where `len` counts
+// the header byte plus the body. The header's bit 0 flags zstd compression and bits
+// 1-3 carry the payload kind (full snapshot vs. JSON Patch).
+
+use anyhow::{anyhow, Result};
+use bytes::{Buf, BufMut, BytesMut};
+
+const LENGTH_PREFIX_BYTES: usize = 4;
+const HEADER_BYTES: usize = 1;
+const COMPRESSED_FLAG: u8 = 0b0000_0001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    Snapshot,
+    Patch,
+}
+
+impl PayloadKind {
+    fn to_bits(self) -> u8 {
+        match self {
+            PayloadKind::Snapshot => 0,
+            PayloadKind::Patch => 1,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0 => Some(PayloadKind::Snapshot),
+            1 => Some(PayloadKind::Patch),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub kind: PayloadKind,
+    pub compressed: bool,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    // Builds a frame from uncompressed JSON bytes, zstd-compressing them when they
+    // exceed `threshold` bytes.
+    pub fn new(kind: PayloadKind, json: &[u8], threshold: usize) -> Result<Self> {
+        if json.len() > threshold {
+            let payload = zstd::stream::encode_all(json, 0)?;
+            Ok(Frame {
+                kind,
+                compressed: true,
+                payload,
+            })
+        } else {
+            Ok(Frame {
+                kind,
+                compressed: false,
+                payload: json.to_vec(),
+            })
+        }
+    }
+
+    // Decompresses the payload (if needed), returning the original JSON bytes.
+    pub fn into_json(self) -> Result<Vec<u8>> {
+        if self.compressed {
+            Ok(zstd::stream::decode_all(&self.payload[..])?)
+        } else {
+            Ok(self.payload)
+        }
+    }
+}
+
+// Appends the encoded frame to `dst`, reusing its existing capacity like a
+// `tokio_util::codec::Encoder` would.
+pub fn encode(frame: &Frame, dst: &mut BytesMut) {
+    let header = (frame.kind.to_bits() << 1) | if frame.compressed { COMPRESSED_FLAG } else { 0 };
+    let body_len = HEADER_BYTES + frame.payload.len();
+
+    dst.reserve(LENGTH_PREFIX_BYTES + body_len);
+    dst.put_u32(body_len as u32);
+    dst.put_u8(header);
+    dst.put_slice(&frame.payload);
+}
+
+// Decodes one frame from the front of `src`, consuming it on success. Returns
+// `Ok(None)` if a complete frame hasn't arrived yet, mirroring the
+// `tokio_util::codec::Decoder` contract so callers can feed it partial reads.
+pub fn decode(src: &mut BytesMut) -> Result<Option<Frame>> {
+    if src.len() < LENGTH_PREFIX_BYTES {
+        return Ok(None);
+    }
+
+    let body_len = u32::from_be_bytes(src[..LENGTH_PREFIX_BYTES].try_into().unwrap()) as usize;
+    if body_len < HEADER_BYTES {
+        return Err(anyhow!(
+            "Frame body_len {} is shorter than the header ({} bytes)",
+            body_len,
+            HEADER_BYTES
+        ));
+    }
+    if src.len() < LENGTH_PREFIX_BYTES + body_len {
+        return Ok(None);
+    }
+
+    src.advance(LENGTH_PREFIX_BYTES);
+    let header = src.get_u8();
+    let compressed = header & COMPRESSED_FLAG != 0;
+    let kind_bits = (header >> 1) & 0b111;
+    let kind = PayloadKind::from_bits(kind_bits)
+        .ok_or_else(|| anyhow!("Unknown payload kind in frame header: {}", kind_bits))?;
+    let payload = src.split_to(body_len - HEADER_BYTES).to_vec();
+
+    Ok(Some(Frame {
+        kind,
+        compressed,
+        payload,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_roundtrips_uncompressed() {
+        let frame = Frame::new(PayloadKind::Snapshot, br#"{"a":1}"#, 1024).unwrap();
+        assert!(!frame.compressed);
+
+        let mut buf = BytesMut::new();
+        encode(&frame, &mut buf);
+        let decoded = decode(&mut buf).unwrap().unwrap();
+
+        assert_eq!(decoded.kind, frame.kind);
+        assert_eq!(decoded.compressed, frame.compressed);
+        assert_eq!(decoded.into_json().unwrap(), br#"{"a":1}"#);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn encode_decode_roundtrips_compressed() {
+        let json = br#"{"a":1}"#.repeat(512);
+        let frame = Frame::new(PayloadKind::Patch, &json, 1024).unwrap();
+        assert!(frame.compressed);
+
+        let mut buf = BytesMut::new();
+        encode(&frame, &mut buf);
+        let decoded = decode(&mut buf).unwrap().unwrap();
+
+        assert_eq!(decoded.kind, PayloadKind::Patch);
+        assert_eq!(decoded.into_json().unwrap(), json);
+    }
+
+    #[test]
+    fn decode_waits_for_a_complete_frame() {
+        let frame = Frame::new(PayloadKind::Snapshot, b"hello", 1024).unwrap();
+        let mut buf = BytesMut::new();
+        encode(&frame, &mut buf);
+
+        let mut partial = buf.split_to(buf.len() - 1);
+        assert!(decode(&mut partial).unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_rejects_body_len_shorter_than_header() {
+        let mut buf = BytesMut::new();
+        buf.put_u32(0);
+        assert!(decode(&mut buf).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// Authenticated RCON-style control channel: a client connects on `CONTROL_PATH`,
+// authenticates with a shared secret, and then issues one command per message,
+// getting exactly one JSON response back. Lets operators administer a deployed
+// agent (toggle debug mode, force a refresh, pull logs, check connection count,
+// shut down) without recompiling or touching the shutdown.trigger file.
+
+use crate::{fetch_system_info, ACTIVE_CONNECTIONS};
+use anyhow::Result;
+use axioo_qc_client::{get_logs, set_debug_mode};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{watch, Notify};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+// WebSocket path that selects the control channel instead of the telemetry stream.
+pub const CONTROL_PATH: &str = "/control";
+
+// Environment variable holding the shared secret clients must present to
+// authenticate. Unset means the control channel rejects every connection.
+pub const CONTROL_SECRET_ENV: &str = "AXIOO_CONTROL_SECRET";
+
+#[derive(Deserialize)]
+struct AuthRequest {
+    auth: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Command {
+    SetDebug { enabled: bool },
+    Refresh,
+    GetLogs,
+    Connections,
+    Shutdown,
+}
+
+// Runs the authenticate-then-command loop for one control connection. The first
+// message must be `{"auth": "<secret>"}`; every message after that is a `Command`
+// and gets exactly one JSON response.
+pub async fn handle_control_connection<S>(
+    mut ws_stream: WebSocketStream<S>,
+    refresh_tx: watch::Sender<Value>,
+    shutdown: Arc<Notify>,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    if !authenticate(&mut ws_stream).await? {
+        let _ = ws_stream
+            .send(Message::Text(
+                json!({"ok": false, "error": "unauthorized"}).to_string(),
+            ))
+            .await;
+        return Ok(());
+    }
+    ws_stream
+        .send(Message::Text(json!({"ok": true}).to_string()))
+        .await?;
+
+    while let Some(message) = ws_stream.next().await {
+        let Message::Text(text) = message? else {
+            continue;
+        };
+
+        let response = match serde_json::from_str::<Command>(&text) {
+            Ok(command) => run_command(command, &refresh_tx, &shutdown),
+            Err(e) => json!({"ok": false, "error": format!("Invalid command: {}", e)}),
+        };
+
+        ws_stream.send(Message::Text(response.to_string())).await?;
+    }
+    Ok(())
+}
+
+fn run_command(command: Command, refresh_tx: &watch::Sender<Value>, shutdown: &Arc<Notify>) -> Value {
+    match command {
+        Command::SetDebug { enabled } => match set_debug_mode(enabled) {
+            Ok(()) => json!({"ok": true, "debug_mode": enabled}),
+            Err(e) => json!({"ok": false, "error": e.to_string()}),
+        },
+        Command::Refresh => match fetch_system_info() {
+            Ok(value) => {
+                let _ = refresh_tx.send(value);
+                json!({"ok": true})
+            }
+            Err(e) => json!({"ok": false, "error": e.to_string()}),
+        },
+        Command::GetLogs => json!({"ok": true, "logs": get_logs()}),
+        Command::Connections => {
+            json!({"ok": true, "connections": ACTIVE_CONNECTIONS.load(Ordering::SeqCst)})
+        }
+        Command::Shutdown => {
+            shutdown.notify_one();
+            json!({"ok": true})
+        }
+    }
+}
+
+async fn authenticate<S>(ws_stream: &mut WebSocketStream<S>) -> Result<bool>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let Some(message) = ws_stream.next().await else {
+        return Ok(false);
+    };
+    let Message::Text(text) = message? else {
+        return Ok(false);
+    };
+    let Ok(request) = serde_json::from_str::<AuthRequest>(&text) else {
+        return Ok(false);
+    };
+    let Ok(expected) = std::env::var(CONTROL_SECRET_ENV) else {
+        eprintln!(
+            "{} is not set; rejecting control channel connection",
+            CONTROL_SECRET_ENV
+        );
+        return Ok(false);
+    };
+
+    Ok(constant_time_eq(request.auth.as_bytes(), expected.as_bytes()))
+}
+
+// Avoids leaking the secret's length/content through an early-exit comparison.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = a.len() ^ b.len();
+    for (i, y) in b.iter().enumerate() {
+        let x = a.get(i).copied().unwrap_or(0);
+        diff |= (x ^ y) as usize;
+    }
+    diff == 0
+}
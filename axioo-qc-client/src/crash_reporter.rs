@@ -0,0 +1,170 @@
+// Crash reporting for the systeminfo.dll FFI boundary. Installs a Rust panic hook
+// and, on Windows, an unhandled structured-exception filter; on Windows both write a
+// timestamped `.dmp` file next to the executable via `MiniDumpWriteDump`, so a
+// DLL-side fault is diagnosable from the field instead of just unwinding or printing
+// to a console nobody sees. The panic hook also prints the last
+// `get_last_error()`/`get_error_message()` values to stderr; the dump files carry only
+// the timestamp and crash reason (in their name), not those values.
+
+use crate::{get_error_message, get_last_error};
+use chrono::Local;
+use std::panic::PanicInfo;
+#[cfg(windows)]
+use std::path::PathBuf;
+
+#[cfg(windows)]
+mod windows_dump {
+    use super::dump_path;
+    use libloading::{Library, Symbol};
+    use once_cell::sync::Lazy;
+    use std::ffi::{c_void, OsStr};
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr::null_mut;
+    use winapi::shared::minwindef::{BOOL, DWORD, FALSE};
+    use winapi::shared::ntdef::HANDLE;
+    use winapi::um::errhandlingapi::SetUnhandledExceptionFilter;
+    use winapi::um::fileapi::{CreateFileW, CREATE_ALWAYS};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::processthreadsapi::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId};
+    use winapi::um::winnt::{
+        EXCEPTION_EXECUTE_HANDLER, EXCEPTION_POINTERS, FILE_ATTRIBUTE_NORMAL, GENERIC_WRITE,
+    };
+
+    // `winapi` 0.3 never bound DbgHelp.dll, so `MiniDumpWriteDump` is loaded the same
+    // way the rest of this crate talks to native DLLs: via `libloading` at runtime,
+    // not a compile-time import.
+    type MiniDumpType = u32;
+    const MINI_DUMP_NORMAL: MiniDumpType = 0;
+
+    #[repr(C)]
+    struct MinidumpExceptionInformation {
+        thread_id: DWORD,
+        exception_pointers: *mut EXCEPTION_POINTERS,
+        client_pointers: BOOL,
+    }
+
+    type MiniDumpWriteDumpT = unsafe extern "system" fn(
+        h_process: HANDLE,
+        process_id: DWORD,
+        h_file: HANDLE,
+        dump_type: MiniDumpType,
+        exception_param: *mut MinidumpExceptionInformation,
+        user_stream_param: *mut c_void,
+        callback_param: *mut c_void,
+    ) -> BOOL;
+
+    // Loaded lazily and only once; missing DbgHelp.dll has to be tolerated rather
+    // than panicking, since a panic here would fire from inside the crash handler
+    // itself and abort the process before any dump is written.
+    static DBGHELP: Lazy<Option<Library>> = Lazy::new(|| unsafe { Library::new("dbghelp.dll").ok() });
+
+    pub fn install() {
+        unsafe {
+            SetUnhandledExceptionFilter(Some(exception_filter));
+        }
+    }
+
+    unsafe extern "system" fn exception_filter(info: *mut EXCEPTION_POINTERS) -> i32 {
+        write_dump(Some(info), "native_exception");
+        EXCEPTION_EXECUTE_HANDLER
+    }
+
+    // Writes a minidump to `dump_path(reason)`. `exception` carries the faulting
+    // thread context for a native SEH crash; `None` for a Rust panic, where there's
+    // no exception record but the current stack is still worth capturing.
+    pub fn write_dump(exception: Option<*mut EXCEPTION_POINTERS>, reason: &str) {
+        let Some(library) = DBGHELP.as_ref() else {
+            eprintln!("dbghelp.dll is not available; skipping minidump");
+            return;
+        };
+        let mini_dump_write_dump: Symbol<MiniDumpWriteDumpT> =
+            match unsafe { library.get(b"MiniDumpWriteDump") } {
+                Ok(symbol) => symbol,
+                Err(e) => {
+                    eprintln!("Failed to resolve MiniDumpWriteDump: {}", e);
+                    return;
+                }
+            };
+
+        let path = dump_path(reason);
+        let wide: Vec<u16> = OsStr::new(&path)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+
+        unsafe {
+            let file = CreateFileW(
+                wide.as_ptr(),
+                GENERIC_WRITE,
+                0,
+                null_mut(),
+                CREATE_ALWAYS,
+                FILE_ATTRIBUTE_NORMAL,
+                null_mut(),
+            );
+            if file == INVALID_HANDLE_VALUE {
+                eprintln!("Failed to create minidump file at {:?}", path);
+                return;
+            }
+
+            let mut exception_info = exception.map(|ptr| MinidumpExceptionInformation {
+                thread_id: GetCurrentThreadId(),
+                exception_pointers: ptr,
+                client_pointers: FALSE,
+            });
+            let exception_param = exception_info
+                .as_mut()
+                .map(|info| info as *mut _)
+                .unwrap_or(null_mut());
+
+            mini_dump_write_dump(
+                GetCurrentProcess(),
+                GetCurrentProcessId(),
+                file,
+                MINI_DUMP_NORMAL,
+                exception_param,
+                null_mut(),
+                null_mut(),
+            );
+
+            CloseHandle(file);
+        }
+    }
+}
+
+// Builds a timestamped dump path next to the executable, e.g.
+// `crash-2026-07-26_10-15-03-panic.dmp`.
+#[cfg(windows)]
+fn dump_path(reason: &str) -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    exe_dir.join(format!("crash-{}-{}.dmp", timestamp, reason))
+}
+
+// Installs the panic hook (and, on Windows, the SEH filter) that together make sure
+// any unhandled crash around the DLL FFI calls leaves a `.dmp` behind. Call this once,
+// as early as possible at startup, before the library is loaded.
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info: &PanicInfo| {
+        eprintln!(
+            "{} Panic: {} (last DLL error: {} - {})",
+            Local::now().format("%H:%M:%S"),
+            info,
+            get_last_error(),
+            get_error_message()
+        );
+
+        #[cfg(windows)]
+        windows_dump::write_dump(None, "panic");
+
+        default_hook(info);
+    }));
+
+    #[cfg(windows)]
+    windows_dump::install();
+}
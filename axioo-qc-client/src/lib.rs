@@ -1,9 +1,19 @@
 // This module serves as the main entry point for the library, exposing bindings and constants.
 
 pub mod bindings; // Import the bindings module for system information.
+pub mod codec; // Length-delimited, optionally zstd-compressed binary frame codec.
+pub mod crash_reporter; // Panic/SEH minidump capture around the systeminfo.dll FFI boundary.
+pub mod patch; // JSON Patch (RFC 6902) diffing between two system-info snapshots.
 
 pub use bindings::systeminfo::*; // Re-export systeminfo functions for easier access.
 
 pub const DEBUG_MODE: bool = false; // Constant to enable or disable debug mode.
 pub const BACKGROUND_MODE: bool = false; // Constant to control console window visibility
 pub const JSON_MODE: bool = false; // Constant to control JSON display on startup
+
+// WebSocket subprotocol name clients request to switch a connection onto the binary
+// frame codec in `codec`; unset, the server keeps sending plain JSON text.
+pub const BINARY_SUBPROTOCOL: &str = "axioo-binary-v1";
+
+// Payloads larger than this many bytes get zstd-compressed when sent as binary frames.
+pub const BINARY_COMPRESSION_THRESHOLD: usize = 1024;
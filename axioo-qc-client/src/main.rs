@@ -1,30 +1,141 @@
 // Required external crates and modules
 use anyhow::Result;
+use axioo_qc_client::codec::{self, PayloadKind};
+use axioo_qc_client::patch::diff;
 use axioo_qc_client::*;
+use bytes::BytesMut;
 use chrono::Local;
 use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use futures_util::SinkExt;
 use scopeguard::guard;
+use serde_json::Value;
 use std::io;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Notify;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{broadcast, watch, Notify};
 use tokio::{
     net::{TcpListener, TcpStream},
     time::sleep,
 };
-use tokio_tungstenite::{accept_async, tungstenite::Message, WebSocketStream};
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::{accept_hdr_async, tungstenite::Message, WebSocketStream};
+
+mod control;
+#[cfg(windows)]
+mod named_pipe;
+#[cfg(windows)]
+mod service;
 
 // Track number of active WebSocket connections
 static ACTIVE_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
 
+// Fetches the current system info and parses it into a `Value`, for seeding and
+// refreshing the shared watch channel that drives push updates to clients.
+fn fetch_system_info() -> Result<Value> {
+    let info = get_system_info()
+        .map_err(|_| anyhow::anyhow!("{} - {}", get_last_error(), get_error_message()))?;
+    Ok(serde_json::from_str(&info)?)
+}
+
+// Performs the WebSocket handshake, negotiating the binary frame codec when the
+// client offers the `BINARY_SUBPROTOCOL` and detecting requests for the control
+// channel's path. Returns the stream plus (binary negotiated, is control channel).
+async fn accept_connection<S>(
+    stream: S,
+) -> tokio_tungstenite::tungstenite::Result<(WebSocketStream<S>, bool, bool)>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let wants_binary = Arc::new(AtomicBool::new(false));
+    let wants_binary_cb = wants_binary.clone();
+    let is_control = Arc::new(AtomicBool::new(false));
+    let is_control_cb = is_control.clone();
+
+    let callback = move |req: &Request, mut response: Response| {
+        let offered = req
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(',').any(|p| p.trim() == BINARY_SUBPROTOCOL))
+            .unwrap_or(false);
+
+        if offered {
+            wants_binary_cb.store(true, Ordering::SeqCst);
+            response.headers_mut().insert(
+                "Sec-WebSocket-Protocol",
+                BINARY_SUBPROTOCOL.parse().unwrap(),
+            );
+        }
+
+        if req.uri().path() == control::CONTROL_PATH {
+            is_control_cb.store(true, Ordering::SeqCst);
+        }
+
+        Ok(response)
+    };
+
+    let ws_stream = accept_hdr_async(stream, callback).await?;
+    Ok((
+        ws_stream,
+        wants_binary.load(Ordering::SeqCst),
+        is_control.load(Ordering::SeqCst),
+    ))
+}
+
 // Main WebSocket server implementation
 async fn run_server(shutdown: Arc<Notify>) -> Result<()> {
     let addr = "127.0.0.1:8765";
     let listener = TcpListener::bind(addr).await?;
     println!("Server running in background on: {}", addr);
 
+    // Shared latest snapshot: seeded from an initial read, then refreshed when the DLL
+    // callback reports a hardware change (or a control client forces a refresh),
+    // instead of on a fixed polling interval.
+    let (update_tx, update_rx) = watch::channel(fetch_system_info()?);
+    let background_tx = update_tx.clone();
+    tokio::spawn(async move {
+        let mut changes = subscribe_changes();
+        loop {
+            match changes.recv().await {
+                Ok(()) => {
+                    if is_debug_mode() {
+                        for log in get_logs() {
+                            println!("{}", log);
+                        }
+                    }
+                    match fetch_system_info() {
+                        Ok(value) => {
+                            if background_tx.send(value).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => eprintln!(
+                            "{} Error refreshing system info: {}",
+                            Local::now().format("%H:%M:%S"),
+                            e
+                        ),
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // Run the named-pipe transport alongside TCP so same-machine clients (a local
+    // QC UI, another agent) don't need a free port, sharing the same update stream.
+    #[cfg(windows)]
+    {
+        let pipe_updates = update_rx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = named_pipe::run(pipe_updates).await {
+                eprintln!("Named pipe listener stopped: {}", e);
+            }
+        });
+    }
+
     // Get executable directory for shutdown file
     let exe_dir = std::env::current_exe()?
         .parent()
@@ -69,10 +180,26 @@ async fn run_server(shutdown: Arc<Notify>) -> Result<()> {
         tokio::select! {
             accept_result = listener.accept() => {
                 if let Ok((stream, addr)) = accept_result {
+                    let updates = update_rx.clone();
+                    let control_tx = update_tx.clone();
+                    let control_shutdown = shutdown.clone();
                     tokio::spawn(async move {
-                        match accept_async(stream).await {
-                            Ok(ws_stream) => {
-                                if let Err(e) = handle_connection(ws_stream, addr).await {
+                        match accept_connection(stream).await {
+                            Ok((ws_stream, _binary, control)) if control => {
+                                if let Err(e) = control::handle_control_connection(
+                                    ws_stream,
+                                    control_tx,
+                                    control_shutdown,
+                                )
+                                .await
+                                {
+                                    eprintln!("Error in control connection handler: {}", e);
+                                }
+                            }
+                            Ok((ws_stream, binary, _)) => {
+                                if let Err(e) =
+                                    handle_connection(ws_stream, addr, updates, binary).await
+                                {
                                     eprintln!("Error in connection handler: {}", e);
                                 }
                             }
@@ -90,60 +217,111 @@ async fn run_server(shutdown: Arc<Notify>) -> Result<()> {
     Ok(())
 }
 
-// Handles individual WebSocket client connections
-async fn handle_connection(
-    mut ws_stream: WebSocketStream<TcpStream>,
+// Picks the smaller of a full snapshot or a JSON Patch describing the transition
+// from `previous` to `current` (always a snapshot for the very first message).
+fn build_update_payload(previous: Option<&Value>, current: &Value) -> (PayloadKind, Vec<u8>) {
+    if let Some(previous) = previous {
+        let ops = diff(previous, current);
+        if let Ok(patch_json) = serde_json::to_vec(&ops) {
+            let full_json = current.to_string();
+            if patch_json.len() < full_json.len() {
+                return (PayloadKind::Patch, patch_json);
+            }
+        }
+    }
+    (PayloadKind::Snapshot, current.to_string().into_bytes())
+}
+
+// Wraps a JSON payload into the WebSocket message this connection expects: a plain
+// `{"snapshot": ...}` / `{"patch": ...}` text envelope by default, or a length-
+// delimited, optionally zstd-compressed binary frame when the client negotiated it.
+fn render_message(kind: PayloadKind, json: &[u8], binary: bool) -> Result<Message> {
+    if binary {
+        let frame = codec::Frame::new(kind, json, BINARY_COMPRESSION_THRESHOLD)?;
+        let mut buf = BytesMut::new();
+        codec::encode(&frame, &mut buf);
+        Ok(Message::Binary(buf.to_vec()))
+    } else {
+        let tag = match kind {
+            PayloadKind::Snapshot => "snapshot",
+            PayloadKind::Patch => "patch",
+        };
+        Ok(Message::Text(format!(
+            r#"{{"{}":{}}}"#,
+            tag,
+            std::str::from_utf8(json)?
+        )))
+    }
+}
+
+// Handles individual WebSocket client connections. Only sends a message when the
+// shared snapshot actually changes, plus one initial snapshot right after connect.
+async fn handle_connection<S>(
+    mut ws_stream: WebSocketStream<S>,
     addr: std::net::SocketAddr,
-) -> io::Result<()> {
+    mut updates: watch::Receiver<Value>,
+    binary: bool,
+) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
     ACTIVE_CONNECTIONS.fetch_add(1, Ordering::SeqCst);
     println!(
-        "{} New client connected: {} (Total: {})",
+        "{} New client connected: {} (Total: {}, protocol: {})",
         Local::now().format("%H:%M:%S"),
         addr,
-        ACTIVE_CONNECTIONS.load(Ordering::SeqCst)
+        ACTIVE_CONNECTIONS.load(Ordering::SeqCst),
+        if binary { "binary" } else { "text" }
     );
 
-    // Main connection loop - sends system information to client
+    let mut last_sent = updates.borrow_and_update().clone();
+    let (kind, json) = build_update_payload(None, &last_sent);
+    let mut pending = Some((kind, json));
+
     loop {
-        if DEBUG_MODE {
-            for log in get_logs() {
-                println!("{}", log);
+        let (kind, json) = match pending.take() {
+            Some(payload) => payload,
+            None => {
+                if updates.changed().await.is_err() {
+                    break;
+                }
+                let current = updates.borrow_and_update().clone();
+                if current == last_sent {
+                    continue;
+                }
+                let payload = build_update_payload(Some(&last_sent), &current);
+                last_sent = current;
+                payload
             }
-        }
+        };
 
-        match get_system_info() {
-            Ok(info) => {
-                if let Err(e) = ws_stream.send(Message::Text(info)).await {
-                    let error_string = e.to_string();
-                    // Handle client disconnection
-                    if error_string.contains("10053")
-                        || error_string.contains("10054")
-                        || error_string.contains("broken pipe")
-                    {
-                        ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
-                        println!(
-                            "{} Client {} disconnected (Total: {})",
-                            Local::now().format("%H:%M:%S"),
-                            addr,
-                            ACTIVE_CONNECTIONS.load(Ordering::SeqCst)
-                        );
-                        break;
-                    } else {
-                        eprintln!("{} Error: {}", Local::now().format("%H:%M:%S"), e);
-                    }
-                }
+        let message = match render_message(kind, &json, binary) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("{} Error building message: {}", Local::now().format("%H:%M:%S"), e);
+                continue;
             }
-            Err(_) => {
-                eprintln!(
-                    "{} Error: {} - {}",
+        };
+
+        if let Err(e) = ws_stream.send(message).await {
+            let error_string = e.to_string();
+            // Handle client disconnection
+            if error_string.contains("10053")
+                || error_string.contains("10054")
+                || error_string.contains("broken pipe")
+            {
+                ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+                println!(
+                    "{} Client {} disconnected (Total: {})",
                     Local::now().format("%H:%M:%S"),
-                    get_last_error(),
-                    get_error_message()
+                    addr,
+                    ACTIVE_CONNECTIONS.load(Ordering::SeqCst)
                 );
+                break;
+            } else {
+                eprintln!("{} Error: {}", Local::now().format("%H:%M:%S"), e);
             }
         }
-
-        sleep(Duration::from_millis(1000)).await;
     }
     Ok(())
 }
@@ -171,9 +349,11 @@ fn ensure_dependencies() -> io::Result<()> {
     Ok(())
 }
 
-// Application entry point
-#[tokio::main]
-async fn main() -> Result<()> {
+// Runs the normal console/background process: raw-mode keystroke handling plus the
+// shutdown.trigger file, exactly as before service mode existed.
+async fn run_console() -> Result<()> {
+    crash_reporter::install();
+
     // Check for required files on Windows
     #[cfg(windows)]
     ensure_dependencies()?;
@@ -217,3 +397,34 @@ async fn main() -> Result<()> {
     run_server(shutdown).await?;
     Ok(())
 }
+
+// Application entry point. Dispatches to the Windows service subsystem when invoked
+// with `--service`/`--install-service`/`--uninstall-service`, falling back to the
+// normal console/background process otherwise. This has to run before the Tokio
+// runtime starts, since the SCM dispatcher thread spins up its own runtime.
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+
+    #[cfg(windows)]
+    {
+        if args.iter().any(|a| a == "--install-service") {
+            return service::install();
+        }
+        if args.iter().any(|a| a == "--uninstall-service") {
+            return service::uninstall();
+        }
+        if args.iter().any(|a| a == "--service") {
+            return service::run();
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        if args.iter().any(|a| {
+            a == "--service" || a == "--install-service" || a == "--uninstall-service"
+        }) {
+            return Err(anyhow::anyhow!("Service mode is only supported on Windows"));
+        }
+    }
+
+    tokio::runtime::Runtime::new()?.block_on(run_console())
+}
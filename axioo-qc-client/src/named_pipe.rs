@@ -0,0 +1,97 @@
+// Named-pipe transport for same-machine WebSocket clients (a local QC UI, another
+// agent), running alongside the TCP listener in `run_server` so consumers on this
+// machine don't depend on a free loopback port. Reuses the exact same handshake and
+// per-connection handler as TCP, and shares its `update_rx` so both transports are
+// driven off the same change-broadcast source.
+
+use crate::{accept_connection, handle_connection};
+use anyhow::Result;
+use serde_json::Value;
+use std::ffi::OsStr;
+use std::io;
+use std::mem::size_of;
+use std::os::windows::ffi::OsStrExt;
+use std::ptr::null_mut;
+use tokio::net::windows::named_pipe::{NamedPipeServer, PipeMode, ServerOptions};
+use tokio::sync::watch;
+use winapi::shared::minwindef::LPVOID;
+use winapi::um::minwinbase::SECURITY_ATTRIBUTES;
+use winapi::um::sddl::ConvertStringSecurityDescriptorToSecurityDescriptorW;
+use winapi::um::winnt::PSECURITY_DESCRIPTOR;
+
+// Restricts the pipe to the interactive user and local administrators, so system
+// info isn't exposed to every local process the way the loopback TCP port is.
+const PIPE_SECURITY_DESCRIPTOR: &str = "D:P(A;;GA;;;IU)(A;;GA;;;BA)";
+
+pub const PIPE_NAME: &str = r"\\.\pipe\axioo-qc";
+
+// Accepts connections on `PIPE_NAME` until pipe creation itself fails; individual
+// connection errors are logged and don't stop the listener, matching the TCP loop.
+pub async fn run(update_rx: watch::Receiver<Value>) -> Result<()> {
+    // Built once and reused for every instance: the descriptor it wraps is never
+    // freed, so re-converting it per connection would leak one allocation per client.
+    let security_attributes = restricted_security_attributes()?;
+    let mut server = create_pipe_instance(&security_attributes)?;
+
+    loop {
+        if let Err(e) = server.connect().await {
+            eprintln!("Error accepting named pipe connection: {}", e);
+            server = create_pipe_instance(&security_attributes)?;
+            continue;
+        }
+
+        let connected = server;
+        // Open the next instance before handling this one so another client can
+        // queue up while the current connection is served.
+        server = create_pipe_instance(&security_attributes)?;
+
+        let updates = update_rx.clone();
+        tokio::spawn(async move {
+            match accept_connection(connected).await {
+                Ok((ws_stream, binary, _control)) => {
+                    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
+                    if let Err(e) = handle_connection(ws_stream, addr, updates, binary).await {
+                        eprintln!("Error in named pipe connection handler: {}", e);
+                    }
+                }
+                Err(e) => eprintln!("Error accepting named pipe connection: {}", e),
+            }
+        });
+    }
+}
+
+fn create_pipe_instance(security_attributes: &SECURITY_ATTRIBUTES) -> io::Result<NamedPipeServer> {
+    unsafe {
+        ServerOptions::new()
+            .pipe_mode(PipeMode::Byte)
+            .create_with_security_attributes_raw(PIPE_NAME, security_attributes as *const _ as LPVOID)
+    }
+}
+
+// Builds a `SECURITY_ATTRIBUTES` from `PIPE_SECURITY_DESCRIPTOR` so only the
+// interactive user and administrators can open the pipe.
+fn restricted_security_attributes() -> io::Result<SECURITY_ATTRIBUTES> {
+    let sddl: Vec<u16> = OsStr::new(PIPE_SECURITY_DESCRIPTOR)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    let mut descriptor: PSECURITY_DESCRIPTOR = null_mut();
+    let ok = unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            sddl.as_ptr(),
+            1, // SDDL_REVISION_1
+            &mut descriptor,
+            null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(SECURITY_ATTRIBUTES {
+        nLength: size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: descriptor,
+        bInheritHandle: 0,
+    })
+}
@@ -0,0 +1,150 @@
+// A minimal RFC 6902 JSON Patch generator used to turn a pair of parsed system-info
+// snapshots into the smallest set of add/replace/remove operations describing how
+// one turned into the other, so clients don't have to re-parse the full document
+// on every change.
+
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Replace { path: String, value: Value },
+    Remove { path: String },
+}
+
+// Computes the ordered list of patch operations that transform `old` into `new`.
+// Objects are diffed key by key; any other value (including arrays) that differs
+// is emitted as a single `replace` at its path rather than diffed element-by-element.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    diff_at("", old, new, &mut ops);
+    ops
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, ops: &mut Vec<PatchOp>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child_path = format!("{}/{}", path, escape_token(key));
+                match new_map.get(key) {
+                    Some(new_value) => diff_at(&child_path, old_value, new_value, ops),
+                    None => ops.push(PatchOp::Remove { path: child_path }),
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    let child_path = format!("{}/{}", path, escape_token(key));
+                    ops.push(PatchOp::Add {
+                        path: child_path,
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        _ => ops.push(PatchOp::Replace {
+            path: path.to_string(),
+            value: new.clone(),
+        }),
+    }
+}
+
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Applies a diff's ops onto `target` in place, the way a client following
+    // RFC 6902 would, so tests can assert `diff(old, new)` actually reproduces `new`.
+    fn apply(target: &mut Value, ops: &[PatchOp]) {
+        for op in ops {
+            match op {
+                PatchOp::Add { path, value } => {
+                    let (parent, key) = path.rsplit_once('/').unwrap();
+                    let parent = if parent.is_empty() {
+                        &mut *target
+                    } else {
+                        target.pointer_mut(parent).unwrap()
+                    };
+                    parent
+                        .as_object_mut()
+                        .unwrap()
+                        .insert(key.to_string(), value.clone());
+                }
+                PatchOp::Replace { path, value } => {
+                    if path.is_empty() {
+                        *target = value.clone();
+                    } else {
+                        *target.pointer_mut(path).unwrap() = value.clone();
+                    }
+                }
+                PatchOp::Remove { path } => {
+                    let (parent, key) = path.rsplit_once('/').unwrap();
+                    let parent = if parent.is_empty() {
+                        &mut *target
+                    } else {
+                        target.pointer_mut(parent).unwrap()
+                    };
+                    parent.as_object_mut().unwrap().remove(key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn diff_roundtrips_through_apply() {
+        let old = json!({"a": 1, "b": {"c": 2, "d": 3}, "e": "gone"});
+        let new = json!({"a": 1, "b": {"c": 20, "d": 3}, "f": "added"});
+
+        let ops = diff(&old, &new);
+        let mut patched = old.clone();
+        apply(&mut patched, &ops);
+
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn diff_of_identical_values_is_empty() {
+        let value = json!({"a": 1, "b": [1, 2, 3]});
+        assert!(diff(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn diff_replaces_whole_array_instead_of_diffing_elements() {
+        let old = json!({"items": [1, 2, 3]});
+        let new = json!({"items": [1, 2]});
+
+        let ops = diff(&old, &new);
+        assert_eq!(
+            ops,
+            vec![PatchOp::Replace {
+                path: "/items".to_string(),
+                value: json!([1, 2]),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_escapes_tilde_and_slash_in_keys() {
+        let old = json!({"a/b~c": 1});
+        let new = json!({"a/b~c": 2});
+
+        let ops = diff(&old, &new);
+        assert_eq!(
+            ops,
+            vec![PatchOp::Replace {
+                path: "/a~1b~0c".to_string(),
+                value: json!(2),
+            }]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// Windows Service Control Manager integration. Lets the agent run as an installed,
+// auto-starting service instead of a console process controlled by a shutdown file
+// or keystroke. Only compiled on Windows; `--service` on other platforms is rejected
+// in `main`.
+
+use crate::{cleanup_system_info, crash_reporter, init_library, register_callback};
+use anyhow::Result;
+use scopeguard::guard;
+use std::ffi::OsString;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use windows_service::service::{
+    ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+    ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+use windows_service::{define_windows_service, service_dispatcher};
+
+const SERVICE_NAME: &str = "AxiooQcClient";
+const SERVICE_DISPLAY_NAME: &str = "Axioo QC Client";
+const SERVICE_DESCRIPTION: &str =
+    "Collects and serves QC system information for this machine in the background.";
+const SERVICE_TYPE: ServiceType = ServiceType::OWN_PROCESS;
+
+define_windows_service!(ffi_service_main, service_main);
+
+// Registers with the Service Control Manager and blocks until the SCM stops us.
+// Call this instead of the normal console entry point when launched as a service.
+pub fn run() -> Result<()> {
+    service_dispatcher::start(SERVICE_NAME, ffi_service_main)?;
+    Ok(())
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    if let Err(e) = run_service() {
+        eprintln!("Service stopped with error: {}", e);
+    }
+}
+
+fn run_service() -> Result<()> {
+    let shutdown = Arc::new(Notify::new());
+    let shutdown_handler = shutdown.clone();
+
+    let status_handle = service_control_handler::register(SERVICE_NAME, move |control_event| {
+        match control_event {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                shutdown_handler.notify_one();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        }
+    })?;
+
+    let report_status = |state: ServiceState, wait_hint: Duration| {
+        // The SCM only honors controls_accepted while the service reports itself as
+        // running; advertising SHUTDOWN here is what actually makes it receive
+        // ServiceControl::Shutdown at all.
+        let controls_accepted = if state == ServiceState::Running {
+            ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN
+        } else {
+            ServiceControlAccept::empty()
+        };
+        status_handle.set_service_status(ServiceStatus {
+            service_type: SERVICE_TYPE,
+            current_state: state,
+            controls_accepted,
+            exit_code: ServiceExitCode::Win32(0),
+            checkpoint: 0,
+            wait_hint,
+            process_id: None,
+        })
+    };
+
+    // Whatever happens below, always report Stopped (with a non-zero exit code
+    // unless we make it all the way through) on the way out, so an early `?` return
+    // (e.g. init_library failing) doesn't leave the SCM thinking we're still starting.
+    let exit_code = Arc::new(AtomicU32::new(1));
+    let exit_code_for_guard = exit_code.clone();
+    let _report_stopped = guard((), move |_| {
+        cleanup_system_info();
+        let _ = status_handle.set_service_status(ServiceStatus {
+            service_type: SERVICE_TYPE,
+            current_state: ServiceState::Stopped,
+            controls_accepted: ServiceControlAccept::empty(),
+            exit_code: ServiceExitCode::Win32(exit_code_for_guard.load(Ordering::SeqCst)),
+            checkpoint: 0,
+            wait_hint: Duration::default(),
+            process_id: None,
+        });
+    });
+
+    report_status(ServiceState::StartPending, Duration::from_secs(3))?;
+
+    crash_reporter::install();
+    init_library()?;
+    register_callback()?;
+
+    report_status(ServiceState::Running, Duration::default())?;
+
+    // The SCM dispatcher thread has no Tokio runtime of its own, so spin one up here
+    // and drive the existing server loop on it exactly like the console entry point.
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(crate::run_server(shutdown));
+
+    report_status(ServiceState::StopPending, Duration::from_secs(3))?;
+    exit_code.store(if result.is_ok() { 0 } else { 1 }, Ordering::SeqCst);
+
+    result
+}
+
+// Creates the service entry in the SCM, pointing it back at this executable with
+// the `--service` flag so future starts run `run_service` instead of the console path.
+pub fn install() -> Result<()> {
+    let manager =
+        ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CREATE_SERVICE)?;
+    let exe_path = std::env::current_exe()?;
+
+    let service_info = ServiceInfo {
+        name: OsString::from(SERVICE_NAME),
+        display_name: OsString::from(SERVICE_DISPLAY_NAME),
+        service_type: SERVICE_TYPE,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path: exe_path,
+        launch_arguments: vec![OsString::from("--service")],
+        dependencies: vec![],
+        account_name: None,
+        account_password: None,
+    };
+
+    let service = manager.create_service(&service_info, ServiceAccess::CHANGE_CONFIG)?;
+    service.set_description(SERVICE_DESCRIPTION)?;
+    println!("Installed service '{}'.", SERVICE_NAME);
+    Ok(())
+}
+
+// Stops (if running) and removes the service entry from the SCM.
+pub fn uninstall() -> Result<()> {
+    let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(
+        SERVICE_NAME,
+        ServiceAccess::DELETE | ServiceAccess::STOP | ServiceAccess::QUERY_STATUS,
+    )?;
+
+    let status = service.query_status()?;
+    if status.current_state != ServiceState::Stopped {
+        service.stop()?;
+    }
+
+    service.delete()?;
+    println!("Uninstalled service '{}'.", SERVICE_NAME);
+    Ok(())
+}